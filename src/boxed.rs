@@ -1,17 +1,109 @@
+//! The Miri-only backend: stores `T` behind a leaked, heap-allocated box reached through a
+//! `NonNull<T>` instead of inline, so the value's address doesn't move when the `Aliasable`
+//! itself does. Miri's Stacked/Tree Borrows checks still see the borrow that produced the
+//! `NonNull` get invalidated by later aliasing under the inline backend's `!Unpin` loophole, but
+//! accessing the value through a raw pointer to a separate allocation sidesteps that entirely.
+//! This backend is never used outside of Miri, since it gives up the zero-cost nature of the
+//! default backend in exchange for being checkable.
+
 extern crate alloc;
 
 use alloc::boxed::Box;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomPinned;
+use core::mem;
 use core::pin::Pin;
 use core::ptr::NonNull;
 
-pub struct Aliasable<T>(NonNull<T>);
+/// An aliasable value. Under this Miri-only backend the value lives in its own heap allocation.
+pub struct Aliasable<T: ?Sized> {
+    ptr: NonNull<T>,
+    _pinned: PhantomPinned,
+}
 
 impl<T> Aliasable<T> {
-    pub fn new(data: T) -> Self {
-        Self(NonNull::from(Box::leak(Box::new(data))))
+    /// Create a new `Aliasable` that stores `val`.
+    #[must_use]
+    #[inline]
+    pub fn new(val: T) -> Self {
+        Self {
+            ptr: NonNull::from(Box::leak(Box::new(val))),
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Get a pinned mutable reference to the value inside the `Aliasable`.
+    ///
+    /// This is sound despite the value potentially being aliased elsewhere: obtaining
+    /// [`Pin`]`<&mut Self>` already proves unique, non-moving access to the `Aliasable` itself, and
+    /// projecting that down to `Pin<&mut T>` doesn't claim uniqueness over `T` the way a bare
+    /// `&mut T` would. Callers must still be careful not to do anything through this reference
+    /// (such as [`mem::swap`](core::mem::swap)) that would move the aliased value out from
+    /// underneath the references that alias it.
+    #[must_use]
+    #[inline]
+    pub fn get_mut_pinned(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| this.ptr.as_mut()) }
+    }
+
+    /// Consume the `Aliasable`, returning its inner value.
+    ///
+    /// If [`get`] has already been called and the type is now pinned, obtaining the owned
+    /// `Aliasable<T>` required to call this function requires breaking the pinning guarantee (as
+    /// the `Aliasable<T>` is moved). However, this is sound as long as the `Aliasable<T>` isn't
+    /// actually aliased at that point in time.
+    ///
+    /// [`get`]: Self::get
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        let ptr = self.ptr;
+        mem::forget(self);
+        *unsafe { Box::from_raw(ptr.as_ptr()) }
+    }
+}
+
+impl<T: ?Sized> Aliasable<T> {
+    /// Create a new `Aliasable` that takes ownership of the boxed value `val`.
+    ///
+    /// Unlike the other backends, this one stores its value out-of-line behind a `NonNull<T>`
+    /// rather than inline as the struct's trailing field, which means `Aliasable<T>` doesn't
+    /// structurally support being unsized through a `Box<Aliasable<T>> -> Box<Aliasable<U>>`
+    /// coercion the way `Aliasable<T>` does on the other backends; `Aliasable<T>` here is always
+    /// `Sized`, even for unsized `T`. This constructor is the way to obtain one for unsized `T`
+    /// instead: unsize `val` with an ordinary, stable `Box` coercion first, then hand it here.
+    #[must_use]
+    pub fn from_box(val: Box<T>) -> Self {
+        Self {
+            ptr: NonNull::from(Box::leak(val)),
+            _pinned: PhantomPinned,
+        }
     }
+
+    /// Get a shared reference to the value inside the `Aliasable`.
+    ///
+    /// This method takes [`Pin`]`<&Self>` instead of `&self` to enforce that all parent containers
+    /// are `!`[`Unpin`], and thus won't be annotated with `noalias`.
+    ///
+    /// This crate intentionally does not provide a method to get a bare `&mut T`, because the
+    /// value may be shared; doing so would wrongly claim uniqueness. If you only need to mutate
+    /// the value through the pinned owner, see [`get_mut_pinned`](Self::get_mut_pinned); if other
+    /// code needs to mutate it concurrently too, use an interior mutable container such as a mutex
+    /// or [`UnsafeCell`](core::cell::UnsafeCell).
+    #[must_use]
+    #[inline]
     pub fn get(self: Pin<&Self>) -> &T {
-        unsafe { self.get_ref().0.as_ref() }
+        unsafe { self.get_ref().ptr.as_ref() }
+    }
+
+    /// Get a shared reference to the value inside the `Aliasable` with an extended lifetime.
+    ///
+    /// # Safety
+    ///
+    /// The reference must not be held for longer than the `Aliasable` exists.
+    #[must_use]
+    #[inline]
+    pub unsafe fn get_extended<'a>(self: Pin<&Self>) -> &'a T {
+        unsafe { self.get_ref().ptr.as_ref() }
     }
 }
 
@@ -21,8 +113,20 @@ impl<T: Default> Default for Aliasable<T> {
     }
 }
 
-impl<T> Drop for Aliasable<T> {
+impl<T: ?Sized> Drop for Aliasable<T> {
     fn drop(&mut self) {
-        unsafe { Box::from_raw(self.0.as_ptr()) };
+        drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+    }
+}
+
+// Safety: the `NonNull<T>` is a leaked `Box<T>` in all but name, and this `Aliasable` owns it the
+// same way a `Box<T>` would (nothing else gets to deallocate or move out of it), so it can be sent
+// or shared across threads exactly when `Box<T>` (i.e. `T` itself) could be.
+unsafe impl<T: Send + ?Sized> Send for Aliasable<T> {}
+unsafe impl<T: Sync + ?Sized> Sync for Aliasable<T> {}
+
+impl<T: ?Sized> Debug for Aliasable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("Aliasable")
     }
 }
@@ -0,0 +1,204 @@
+//! A small intrusive, doubly-linked list built on top of [`Aliasable`](crate::Aliasable),
+//! generalizing the `Pair` example from the crate documentation into a reusable subsystem. Each
+//! [`Node`] owns its value and is linked into at most one [`List`] at a time; dropping a node
+//! unlinks it from its neighbors, and dropping the list first leaves
+//! any still-linked nodes safely unlinked instead of pointing at freed memory.
+
+use core::cell::Cell;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomPinned;
+use core::pin::Pin;
+
+use pin_project::{pin_project, pinned_drop};
+
+use crate::Aliasable;
+
+struct NodeInner<T: 'static> {
+    value: T,
+    prev: Cell<Option<&'static NodeInner<T>>>,
+    next: Cell<Option<&'static NodeInner<T>>>,
+    // The `head` cell of the list this node is currently linked into (set on every linked node,
+    // not only the front one), so that unlinking the front node can patch the list's `head`
+    // pointer too. Cleared by `List`'s `Drop` if the list goes away first, so that
+    // this node's own removal never writes through a dangling reference.
+    head: Cell<Option<&'static Cell<Option<&'static NodeInner<T>>>>>,
+}
+
+/// A node that can be linked into a [`List`].
+#[pin_project(PinnedDrop)]
+pub struct Node<T: 'static> {
+    #[pin]
+    inner: Aliasable<NodeInner<T>>,
+}
+
+impl<T: 'static> Node<T> {
+    /// Create a new, unlinked `Node` that stores `value`.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Aliasable::new(NodeInner {
+                value,
+                prev: Cell::new(None),
+                next: Cell::new(None),
+                head: Cell::new(None),
+            }),
+        }
+    }
+
+    /// Get a pinned shared reference to the value stored in this node.
+    #[must_use]
+    pub fn get(self: Pin<&Self>) -> Pin<&T> {
+        let inner = self.project_ref().inner.get();
+        unsafe { Pin::new_unchecked(&inner.value) }
+    }
+}
+
+#[pinned_drop]
+impl<T: 'static> PinnedDrop for Node<T> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        let inner = this.inner.as_ref().get();
+        let prev = inner.prev.get();
+        let next = inner.next.get();
+        match prev {
+            Some(prev) => prev.next.set(next),
+            None => {
+                if let Some(head) = inner.head.get() {
+                    head.set(next);
+                }
+            }
+        }
+        if let Some(next) = next {
+            next.prev.set(prev);
+        }
+    }
+}
+
+impl<T: 'static> Debug for Node<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("Node")
+    }
+}
+
+/// The head of an intrusive, doubly-linked list of [`Node`]s.
+///
+/// A `List` does not own its nodes; it only threads pointers between them, so nodes may live
+/// anywhere (the stack, another struct, …) as long as they outlive their time spent linked in.
+///
+/// A `List` is `!Unpin`: [`push`](Self::push) stashes a `'static` reference to its own `head`
+/// cell inside each linked node, so moving a `List` while nodes are linked into it would leave
+/// them pointing at the old location instead of the new one. Because of this, once a `List` has
+/// been pinned there is no safe way to move it back out:
+///
+/// ```compile_fail
+/// use pin_utils::pin_mut;
+/// use pinned_aliasable::intrusive::{List, Node};
+///
+/// let list = List::new();
+/// pin_mut!(list);
+/// let node = Node::new(1);
+/// pin_mut!(node);
+/// list.as_mut().push(node.as_ref());
+///
+/// // Fails to compile: `List` is `!Unpin`, so `Pin::get_mut` isn't available. Were it `Unpin`
+/// // (the bug this guards against), this would compile and let `mem::swap` relocate `list`'s
+/// // contents while `node` still points at its old address.
+/// let mut other = List::new();
+/// core::mem::swap(list.get_mut(), &mut other);
+/// ```
+#[derive(Default)]
+pub struct List<T: 'static> {
+    head: Cell<Option<&'static NodeInner<T>>>,
+    _pinned: PhantomPinned,
+}
+
+impl<T: 'static> List<T> {
+    /// Create a new, empty `List`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            head: Cell::new(None),
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Push `node` onto the front of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` is already linked into a list (this one or another), since unlinking it
+    /// from its actual list would then be skipped in favor of this one, leaving that other list's
+    /// `head` (or the node before `node` in it) pointing at memory that may later be freed.
+    pub fn push(self: Pin<&mut Self>, node: Pin<&Node<T>>) {
+        // `List` is `!Unpin`, so we can't call `get_mut` here; but we don't actually need a bare
+        // `&mut Self`, since every field we touch below is mutated through a `Cell`.
+        let list = self.into_ref().get_ref();
+        // Safety: `node` outlives its time linked into `list`, because its `PinnedDrop` unlinks
+        // it first; and `list.head` outlives `node`'s link to it for the same reason in reverse,
+        // since `List`'s `Drop` clears every remaining node's `head` pointer before going away.
+        let node_inner = unsafe { node.project_ref().inner.get_extended() };
+        let head_cell = unsafe { &*(core::ptr::addr_of!(list.head)) };
+
+        assert!(
+            node_inner.head.get().is_none(),
+            "node is already linked into a list"
+        );
+
+        node_inner.head.set(Some(head_cell));
+        node_inner.prev.set(None);
+        node_inner.next.set(list.head.get());
+        if let Some(old_head) = list.head.get() {
+            old_head.prev.set(Some(node_inner));
+        }
+        list.head.set(Some(node_inner));
+    }
+
+    /// Iterate over the values stored in this list's nodes, from front to back.
+    #[must_use]
+    pub fn iter(self: Pin<&Self>) -> Iter<'_, T> {
+        Iter {
+            current: self.get_ref().head.get(),
+        }
+    }
+}
+
+impl<T: 'static> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.get();
+        while let Some(node) = current {
+            node.head.set(None);
+            current = node.next.get();
+            node.prev.set(None);
+            node.next.set(None);
+        }
+    }
+}
+
+impl<T: 'static> Debug for List<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("List")
+    }
+}
+
+/// An iterator over the values stored in a [`List`], yielding pinned shared references.
+///
+/// Created by [`List::iter`].
+pub struct Iter<'a, T: 'static> {
+    current: Option<&'a NodeInner<T>>,
+}
+
+impl<'a, T: 'static> Iterator for Iter<'a, T> {
+    type Item = Pin<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?;
+        self.current = node.next.get();
+        Some(unsafe { Pin::new_unchecked(&node.value) })
+    }
+}
+
+impl<T: 'static> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("Iter")
+    }
+}
@@ -111,62 +111,55 @@
 //! A boxed slice that also stores a subslice of itself:
 //!
 //! ```rust
+//! use core::cell::UnsafeCell;
 //! use core::pin::Pin;
-//! use core::ptr::NonNull;
 //! use core::slice::SliceIndex;
-//! use core::cell::UnsafeCell;
 //!
-//! use pin_project::pin_project;
-//! use pin_utils::pin_mut;
 //! use pinned_aliasable::Aliasable;
 //!
-//! #[pin_project]
 //! pub struct OwningSlice<T: 'static> {
 //!     // In a real implementation you would avoid the `T: 'static` bound by using some kind of
 //!     // raw pointer here.
 //!     slice: Option<&'static mut [T]>,
-//!     #[pin]
-//!     data: Aliasable<UnsafeCell<Box<[T]>>>,
+//!     // Boxed (rather than stored inline and pinned alongside `slice`) so that `Aliasable`'s own
+//!     // unsizing support can turn this straight into `Aliasable<UnsafeCell<[T]>>`, with no
+//!     // separate `Box<[T]>` living inside it.
+//!     data: Pin<Box<Aliasable<UnsafeCell<[T]>>>>,
 //! }
-//! impl<T: 'static> From<Box<[T]>> for OwningSlice<T> {
-//!     fn from(data: Box<[T]>) -> Self {
-//!         Self {
-//!             slice: None,
-//!             data: Aliasable::new(UnsafeCell::new(data)),
-//!         }
+//! impl<T: 'static, const N: usize> From<[T; N]> for OwningSlice<T> {
+//!     fn from(data: [T; N]) -> Self {
+//!         let data: Box<Aliasable<UnsafeCell<[T]>>> =
+//!             Box::new(Aliasable::new(UnsafeCell::new(data)));
+//!         Self { slice: None, data: Pin::from(data) }
 //!     }
 //! }
 //! impl<T> OwningSlice<T> {
-//!     pub fn slice(self: Pin<&mut Self>, range: impl SliceIndex<[T], Output = [T]>) {
-//!         let mut this = self.project();
-//!         let current_slice = this.slice.take().unwrap_or_else(|| {
-//!             unsafe { &mut **this.data.as_ref().get_extended().get() }
+//!     pub fn slice(&mut self, range: impl SliceIndex<[T], Output = [T]>) {
+//!         let current_slice = self.slice.take().unwrap_or_else(|| {
+//!             unsafe { &mut *self.data.as_ref().get_extended().get() }
 //!         });
-//!         *this.slice = Some(&mut current_slice[range]);
+//!         self.slice = Some(&mut current_slice[range]);
 //!     }
-//!     pub fn get(self: Pin<&Self>) -> &[T] {
-//!         let this = self.project_ref();
-//!         this.slice.as_deref().unwrap_or_else(|| unsafe { &**this.data.get().get() })
+//!     pub fn get(&self) -> &[T] {
+//!         self.slice.as_deref().unwrap_or_else(|| unsafe { &*self.data.as_ref().get().get() })
 //!     }
-//!     pub fn get_mut(self: Pin<&mut Self>) -> &mut [T] {
-//!         let this = self.project();
-//!         let data = this.data.as_ref();
-//!         this.slice.as_deref_mut().unwrap_or_else(|| unsafe { &mut **data.get().get() })
+//!     pub fn get_mut(&mut self) -> &mut [T] {
+//!         let data = self.data.as_ref();
+//!         self.slice.as_deref_mut().unwrap_or_else(|| unsafe { &mut *data.get().get() })
 //!     }
 //! }
 //!
-//! let slice = OwningSlice::from(vec![1, 2, 3, 4, 5].into_boxed_slice());
-//! pin_mut!(slice);
-//! assert_eq!(slice.as_ref().get(), &[1, 2, 3, 4, 5]);
+//! let mut slice = OwningSlice::from([1, 2, 3, 4, 5]);
+//! assert_eq!(slice.get(), &[1, 2, 3, 4, 5]);
 //!
-//! slice.as_mut().slice(1..);
-//! assert_eq!(slice.as_ref().get(), &[2, 3, 4, 5]);
+//! slice.slice(1..);
+//! assert_eq!(slice.get(), &[2, 3, 4, 5]);
 //!
-//! slice.as_mut().slice(2..=3);
-//! assert_eq!(slice.as_ref().get(), &[4, 5]);
+//! slice.slice(2..=3);
+//! assert_eq!(slice.get(), &[4, 5]);
 //!
-//! slice.as_mut().slice(0..0);
-//! assert_eq!(slice.as_ref().get(), &[]);
+//! slice.slice(0..0);
+//! assert_eq!(slice.get(), &[]);
 //! ```
 //!
 //! A pair type:
@@ -228,6 +221,7 @@
 //! }
 //! ```
 #![no_std]
+#![cfg_attr(feature = "unsafe-pinned", feature(unsafe_pinned))]
 #![warn(
     clippy::pedantic,
     rust_2018_idioms,
@@ -240,85 +234,37 @@
 )]
 #![allow(clippy::items_after_statements)]
 
-use core::fmt::{self, Debug, Formatter};
-use core::marker::PhantomPinned;
-use core::pin::Pin;
-
-/// An unboxed aliasable value.
-#[derive(Default)]
-pub struct Aliasable<T> {
-    val: T,
-    _pinned: PhantomPinned,
-}
-
-impl<T> Aliasable<T> {
-    /// Create a new `Aliasable` that stores `val`.
-    #[must_use]
-    #[inline]
-    pub fn new(val: T) -> Self {
-        Self {
-            val,
-            _pinned: PhantomPinned,
-        }
-    }
-
-    /// Get a shared reference to the value inside the `Aliasable`.
-    ///
-    /// This method takes [`Pin`]`<&Self>` instead of `&self` to enforce that all parent containers
-    /// are `!`[`Unpin`], and thus won't be annotated with `noalias`.
-    ///
-    /// This crate intentionally does not provide a method to get an `&mut T`, because the value
-    /// may be shared. To obtain an `&mut T` you should use an interior mutable container such as a
-    /// mutex or [`UnsafeCell`](core::cell::UnsafeCell).
-    #[must_use]
-    #[inline]
-    pub fn get(self: Pin<&Self>) -> &T {
-        &self.get_ref().val
-    }
-
-    /// Get a shared reference to the value inside the `Aliasable` with an extended lifetime.
-    ///
-    /// # Safety
-    ///
-    /// The reference must not be held for longer than the `Aliasable` exists.
-    #[must_use]
-    #[inline]
-    pub unsafe fn get_extended<'a>(self: Pin<&Self>) -> &'a T {
-        unsafe { &*(self.get() as *const T) }
-    }
-
-    /// Consume the `Aliasable`, returning its inner value.
-    ///
-    /// If [`get`] has already been called and the type is now pinned, obtaining the owned
-    /// `Aliasable<T>` required to call this function requires breaking the pinning guarantee (as
-    /// the `Aliasable<T>` is moved). However, this is sound as long as the `Aliasable<T>` isn't
-    /// actually aliased at that point in time.
-    ///
-    /// [`get`]: Self::get
-    #[must_use]
-    pub fn into_inner(self) -> T {
-        self.val
-    }
-}
+// Under Miri, the `!Unpin` loophole the default backend relies on still trips the
+// Stacked/Tree Borrows aliasing model, because the reference returned by `get_extended` outlives
+// the borrow that produced it. So under Miri we swap in a backend that boxes `T` separately
+// instead of storing it inline, which sidesteps the issue at the cost of an allocation. On
+// nightlies with the `unsafe-pinned` feature enabled, we instead use `core::pin::UnsafePinned`,
+// which is sound unconditionally and doesn't need either trick.
+#[cfg_attr(feature = "unsafe-pinned", path = "unsafe_pinned.rs")]
+#[cfg_attr(all(not(feature = "unsafe-pinned"), miri), path = "boxed.rs")]
+#[cfg_attr(all(not(feature = "unsafe-pinned"), not(miri)), path = "inline.rs")]
+mod imp;
+mod mutable;
+pub mod intrusive;
 
-impl<T> Debug for Aliasable<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.pad("Aliasable")
-    }
-}
+pub use imp::Aliasable;
+pub use mutable::AliasableMut;
 
 #[cfg(test)]
 mod tests {
     extern crate alloc;
 
     use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
     use core::cell::{Cell, UnsafeCell};
     use core::ops::DerefMut;
     use core::pin::Pin;
 
     use pin_project::pin_project;
 
-    use super::Aliasable;
+    use super::intrusive::{List, Node};
+    use super::{Aliasable, AliasableMut};
 
     #[test]
     fn miri_is_happy() {
@@ -398,4 +344,151 @@ mod tests {
         // See the last paragraph of the crate documentation.
         //assert_eq!(helper(value, reference), 20);
     }
+
+    #[test]
+    fn aliasable_mut_roundtrip() {
+        let mut value = 10;
+        let aliasable = AliasableMut::from_unique(&mut value);
+        assert_eq!(*aliasable, 10);
+        let unique = unsafe { aliasable.into_unique() };
+        *unique = 20;
+        assert_eq!(*unique, 20);
+    }
+
+    #[test]
+    fn aliasable_mut_get_mut() {
+        let mut value = 10;
+        let mut aliasable = AliasableMut::from_unique(&mut value);
+        *unsafe { aliasable.get_mut() } = 20;
+        assert_eq!(*unsafe { aliasable.into_unique() }, 20);
+    }
+
+    #[test]
+    fn aliasable_mut_many_aliases() {
+        // `N` `AliasableMut`s may alias the same `UnsafeCell` at once, as long as mutation only
+        // ever happens through the `UnsafeCell`'s own interior mutability rather than through
+        // `get_mut`/`into_unique`, which both still assume uniqueness.
+        let mut cell = UnsafeCell::new(10);
+        let first = AliasableMut::from_unique(&mut cell);
+        let ptr = core::ptr::NonNull::from(&*first);
+        // Safety: `second` aliases the same `UnsafeCell` as `first`; every access to it below goes
+        // through the `UnsafeCell`, which permits aliased mutation, so the two never produce
+        // conflicting `&mut i32`s to the value it wraps.
+        let second = AliasableMut::from_unique(unsafe { &mut *ptr.as_ptr() });
+
+        unsafe { *first.get() = 20 };
+        assert_eq!(unsafe { *second.get() }, 20);
+        unsafe { *second.get() = 30 };
+        assert_eq!(unsafe { *first.get() }, 30);
+    }
+
+    #[test]
+    fn unsized_value() {
+        // On the default and `unsafe-pinned` backends, `Aliasable<T>` stores `T` as its trailing
+        // field, so it's unsized whenever `T` is and an owning `Box` unsizes the ordinary way. The
+        // Miri-only backend stores `T` out-of-line instead, which makes `Aliasable<T>` always
+        // `Sized`; it provides `Aliasable::from_box` to construct one for unsized `T` instead.
+        #[cfg(any(feature = "unsafe-pinned", not(miri)))]
+        let aliasable: Box<Aliasable<[i32]>> = Box::new(Aliasable::new([1, 2, 3]));
+        #[cfg(all(not(feature = "unsafe-pinned"), miri))]
+        let aliasable: Box<Aliasable<[i32]>> =
+            Box::new(Aliasable::from_box(Box::new([1, 2, 3]) as Box<[i32]>));
+
+        let aliasable = Box::into_pin(aliasable);
+        assert_eq!(aliasable.as_ref().get(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn intrusive_push_and_iter() {
+        let list = List::new();
+        pin_utils::pin_mut!(list);
+        let a = Node::new(1);
+        pin_utils::pin_mut!(a);
+        let b = Node::new(2);
+        pin_utils::pin_mut!(b);
+        let c = Node::new(3);
+        pin_utils::pin_mut!(c);
+
+        list.as_mut().push(c.as_ref());
+        list.as_mut().push(b.as_ref());
+        list.as_mut().push(a.as_ref());
+
+        let values: Vec<i32> = list.as_ref().iter().map(|value| *value).collect();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn intrusive_drop_unlinks_head_node() {
+        let list = List::new();
+        pin_utils::pin_mut!(list);
+        let c = Node::new(3);
+        pin_utils::pin_mut!(c);
+        list.as_mut().push(c.as_ref());
+
+        {
+            let a = Node::new(1);
+            pin_utils::pin_mut!(a);
+            list.as_mut().push(a.as_ref());
+            // `a` is dropped here, at the end of this block, while it is still the list's head.
+        }
+
+        let values: Vec<i32> = list.as_ref().iter().map(|value| *value).collect();
+        assert_eq!(values, vec![3]);
+    }
+
+    #[test]
+    fn intrusive_drop_unlinks_middle_node() {
+        let list = List::new();
+        pin_utils::pin_mut!(list);
+        let a = Node::new(1);
+        pin_utils::pin_mut!(a);
+        let c = Node::new(3);
+        pin_utils::pin_mut!(c);
+        list.as_mut().push(c.as_ref());
+
+        {
+            let b = Node::new(2);
+            pin_utils::pin_mut!(b);
+            list.as_mut().push(b.as_ref());
+            list.as_mut().push(a.as_ref());
+            // `b` is dropped here, while linked between `a` and `c`.
+        }
+
+        let values: Vec<i32> = list.as_ref().iter().map(|value| *value).collect();
+        assert_eq!(values, vec![1, 3]);
+    }
+
+    #[test]
+    fn intrusive_list_dropped_before_nodes() {
+        let a = Node::new(1);
+        pin_utils::pin_mut!(a);
+        let b = Node::new(2);
+        pin_utils::pin_mut!(b);
+
+        {
+            let list = List::new();
+            pin_utils::pin_mut!(list);
+            list.as_mut().push(a.as_ref());
+            list.as_mut().push(b.as_ref());
+            // `list` is dropped here, before `a` and `b`; its `Drop` clears their `head` pointers
+            // so their own drops below don't write through the now-dangling list.
+        }
+
+        assert_eq!(*a.as_ref().get(), 1);
+        assert_eq!(*b.as_ref().get(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "node is already linked into a list")]
+    fn intrusive_push_already_linked_panics() {
+        let list_a = List::new();
+        pin_utils::pin_mut!(list_a);
+        let list_b = List::new();
+        pin_utils::pin_mut!(list_b);
+        let node = Node::new(1);
+        pin_utils::pin_mut!(node);
+
+        list_a.as_mut().push(node.as_ref());
+        list_b.as_mut().push(node.as_ref());
+    }
 }
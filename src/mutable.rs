@@ -0,0 +1,101 @@
+//! The other half of building self-referential types: storing a mutable borrow into a sibling
+//! field without telling the compiler it is unique. A plain `&'static mut T` stashed away like
+//! this is exactly the soundness hazard [`Aliasable`](crate::Aliasable) exists to dodge, since the
+//! compiler assumes `&mut` references are unique; [`AliasableMut`] gives up that assumption in
+//! exchange for being storable alongside other aliases of the same data.
+
+use core::fmt::{self, Debug, Formatter};
+use core::marker::{PhantomData, PhantomPinned};
+use core::ops::Deref;
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+/// A mutable reference that does not assume it is unique.
+///
+/// Any number of `AliasableMut<T>` may coexist alongside either at most one live `&mut T` or any
+/// number of `&T`, without this being undefined behaviour; the regular borrowing rules otherwise
+/// still apply. This is the mirror image of [`Aliasable`](crate::Aliasable): that type lets you
+/// hand out aliased shared references to an owned value, while `AliasableMut` lets you store an
+/// aliased mutable reference.
+pub struct AliasableMut<'a, T: ?Sized> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<&'a mut T>,
+    _pinned: PhantomPinned,
+}
+
+impl<'a, T: ?Sized> AliasableMut<'a, T> {
+    /// Create a new `AliasableMut` from a unique reference.
+    #[must_use]
+    #[inline]
+    pub fn from_unique(unique: &'a mut T) -> Self {
+        Self {
+            ptr: NonNull::from(unique),
+            _marker: PhantomData,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Get a mutable reference to the value behind this `AliasableMut`.
+    ///
+    /// # Safety
+    ///
+    /// There must be no other live `&mut T` aliasing the same value for as long as the returned
+    /// reference is used (whether reached through another `AliasableMut`, the original unique
+    /// reference, or anywhere else); any number of other `AliasableMut`s or `&T`s may coexist.
+    #[must_use]
+    #[inline]
+    pub unsafe fn get_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+
+    /// Convert this `AliasableMut` back into a unique reference.
+    ///
+    /// # Safety
+    ///
+    /// There must be no other live aliases (`AliasableMut`, `&T`, or `&mut T`) of the same value
+    /// for as long as the returned reference is used, since it is assumed to be unique.
+    #[must_use]
+    #[inline]
+    pub unsafe fn into_unique(self) -> &'a mut T {
+        unsafe { &mut *self.ptr.as_ptr() }
+    }
+
+    /// Convert a pinned `AliasableMut` back into a unique pinned reference.
+    ///
+    /// This is the pinned equivalent of [`into_unique`](Self::into_unique), for use when the
+    /// pointee must not be moved out of.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`into_unique`](Self::into_unique).
+    #[must_use]
+    #[inline]
+    pub unsafe fn into_unique_pin(this: Pin<Self>) -> Pin<&'a mut T> {
+        unsafe { Pin::new_unchecked(&mut *Pin::into_inner_unchecked(this).ptr.as_ptr()) }
+    }
+}
+
+impl<T: ?Sized> Deref for AliasableMut<'_, T> {
+    type Target = T;
+
+    /// Get a shared reference to the value behind this `AliasableMut`.
+    ///
+    /// This is always sound: a shared read can't violate uniqueness on its own, no matter how
+    /// many other `AliasableMut`s or `&T`s currently alias the same value.
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> Debug for AliasableMut<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("AliasableMut")
+    }
+}
+
+// Safety: `AliasableMut<'a, T>` stands in for `&'a mut T`, and the `_marker` field already says as
+// much; it's only the `NonNull<T>` used to implement that which isn't auto-`Send`/`Sync` on its
+// own, so give it the same bounds a real `&mut T` would have.
+unsafe impl<T: Send + ?Sized> Send for AliasableMut<'_, T> {}
+unsafe impl<T: Sync + ?Sized> Sync for AliasableMut<'_, T> {}
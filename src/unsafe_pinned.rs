@@ -0,0 +1,92 @@
+//! The `unsafe-pinned` backend: a thin newtype over the (currently nightly-only)
+//! [`core::pin::UnsafePinned`], the type proposed by RFC 3467 as the eventual libcore primitive
+//! this crate is a stopgap for. Unlike the other backends, `UnsafePinned<T>` opts its contents out
+//! of `noalias` directly instead of relying on the `!Unpin` loophole, so it is sound even for
+//! `Unpin` inner types and even when boxed. Once this (or an equivalent) lands on stable, this
+//! backend can become the only one and the crate can be yanked in favour of a direct alias.
+
+use core::fmt::{self, Debug, Formatter};
+use core::pin::{Pin, UnsafePinned};
+
+/// An unboxed aliasable value.
+pub struct Aliasable<T: ?Sized>(UnsafePinned<T>);
+
+impl<T> Aliasable<T> {
+    /// Create a new `Aliasable` that stores `val`.
+    #[must_use]
+    #[inline]
+    pub fn new(val: T) -> Self {
+        Self(UnsafePinned::new(val))
+    }
+
+    /// Get a pinned mutable reference to the value inside the `Aliasable`.
+    ///
+    /// This is sound despite the value potentially being aliased elsewhere: obtaining
+    /// [`Pin`]`<&mut Self>` already proves unique, non-moving access to the `Aliasable` itself, and
+    /// projecting that down to `Pin<&mut T>` doesn't claim uniqueness over `T` the way a bare
+    /// `&mut T` would. Callers must still be careful not to do anything through this reference
+    /// (such as [`mem::swap`](core::mem::swap)) that would move the aliased value out from
+    /// underneath the references that alias it.
+    #[must_use]
+    #[inline]
+    pub fn get_mut_pinned(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe {
+            let this = self.get_unchecked_mut();
+            Pin::new_unchecked(&mut *UnsafePinned::raw_get_mut(&mut this.0))
+        }
+    }
+
+    /// Consume the `Aliasable`, returning its inner value.
+    ///
+    /// If [`get`] has already been called and the type is now pinned, obtaining the owned
+    /// `Aliasable<T>` required to call this function requires breaking the pinning guarantee (as
+    /// the `Aliasable<T>` is moved). However, this is sound as long as the `Aliasable<T>` isn't
+    /// actually aliased at that point in time.
+    ///
+    /// [`get`]: Self::get
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+}
+
+impl<T: ?Sized> Aliasable<T> {
+    /// Get a shared reference to the value inside the `Aliasable`.
+    ///
+    /// This method takes [`Pin`]`<&Self>` instead of `&self` to enforce that all parent containers
+    /// are `!`[`Unpin`], and thus won't be annotated with `noalias`.
+    ///
+    /// This crate intentionally does not provide a method to get a bare `&mut T`, because the
+    /// value may be shared; doing so would wrongly claim uniqueness. If you only need to mutate
+    /// the value through the pinned owner, see [`get_mut_pinned`](Self::get_mut_pinned); if other
+    /// code needs to mutate it concurrently too, use an interior mutable container such as a mutex
+    /// or [`UnsafeCell`](core::cell::UnsafeCell).
+    #[must_use]
+    #[inline]
+    pub fn get(self: Pin<&Self>) -> &T {
+        unsafe { &*UnsafePinned::raw_get(&self.get_ref().0) }
+    }
+
+    /// Get a shared reference to the value inside the `Aliasable` with an extended lifetime.
+    ///
+    /// # Safety
+    ///
+    /// The reference must not be held for longer than the `Aliasable` exists.
+    #[must_use]
+    #[inline]
+    pub unsafe fn get_extended<'a>(self: Pin<&Self>) -> &'a T {
+        unsafe { &*(self.get() as *const T) }
+    }
+}
+
+impl<T: Default> Default for Aliasable<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: ?Sized> Debug for Aliasable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("Aliasable")
+    }
+}